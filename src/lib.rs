@@ -1,23 +1,26 @@
 //! ### ❄️ snof
-//! 
+//!
 //! *snof* is a unique ID generator. Loosely based on snowflake ID-s, *snof*
 //! generates 64 bit long identifiers consisting of a 32 bit millisecond-based
 //! timestamp, and 22 bits of sequence distinguishing identifiers generated within
 //! the same millisecond.
-//! 
+//!
 //! The generator uses atomic operations for tracking state, thus it provides a
 //! thread-safe, lock-free way of generating unique ID-s. In case of the sequence
 //! being exhausted, or the clock moving backwards, the generator spins until
 //! validity is restored.
-//! 
+//!
 //! #### Usage
-//! 
+//!
 //! ```rust
+//! use std::sync::Arc;
+//! use std::thread;
+//!
 //! use snof::SnowflakeGenerator;
-//! 
+//!
 //! fn main() {
 //!     let generator = Arc::new(SnowflakeGenerator::new());
-//! 
+//!
 //!     let threads: Vec<_> = (0..4).map(|_| {
 //!         let other_generator = Arc::clone(&generator);
 //!         thread::spawn(move || {
@@ -25,7 +28,7 @@
 //!             println!("thread id: {}", id.0);
 //!         })
 //!     }).collect();
-//! 
+//!
 //!     for t in threads { t.join().unwrap(); }
 //! }
 //! ```
@@ -33,22 +36,347 @@
 use std::cmp::Ordering;
 use std::hint::spin_loop;
 use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Unix timestamp of Jan 01 2026 00:00:00 GMT+0000 in milliseconds.
 const EPOCH: u128 = 1_767_225_600_000;
 /// Out of the 64-bits of the identifier, the last 22 are reserved for the sequence.
 const SEQUENCE_BITS: u32 = 22;
-/// Mask for extracting the sequence bits.
-const SEQUENCE_MASK: u64 = (1 << SEQUENCE_BITS) - 1;
+
+/// Number of bits reserved for the node id in [`SnowflakeGenerator::with_node`].
+///
+/// This block is carved out of the sequence space rather than the timestamp, so the
+/// sequence is effectively narrowed to `SEQUENCE_BITS - NODE_BITS` bits whenever a node
+/// id is configured.
+const NODE_BITS: u32 = 10;
+/// Sequence bit width once the node-id block has been carved out.
+const DISTRIBUTED_SEQUENCE_BITS: u32 = SEQUENCE_BITS - NODE_BITS;
+
+/// The resolved bit layout of a [`SnowflakeGenerator`]: the epoch together with the
+/// widths, shifts and masks derived from it.
+///
+/// A [`SnowflakeLayout`] is produced by [`SnowflakeBuilder::build`] and must be passed
+/// back in to [`Snowflake`]'s extraction methods, since the bit pattern alone cannot
+/// tell a generator's custom layout apart from any other.
+#[derive(Debug, Clone, Copy)]
+pub struct SnowflakeLayout {
+    /// Epoch, in ticks since the UNIX epoch at `resolution`.
+    epoch_ticks: u128,
+    resolution: TimestampResolution,
+    sequence_mask: u64,
+    node_mask: u64,
+    /// Shift applied to the timestamp, i.e. the combined width of the node and sequence
+    /// fields.
+    timestamp_shift: u32,
+    /// Shift applied to the node id, i.e. the width of the sequence field.
+    node_shift: u32,
+    /// Mask covering everything below the timestamp (node id and sequence combined).
+    low_mask: u64,
+}
+
+/// Resolution at which the timestamp field advances.
+///
+/// Raising the resolution gives each sub-millisecond instant its own sequence space,
+/// trading timestamp lifespan for sustained throughput under bursty load; at the default
+/// resolution, the sequence tops out once per millisecond and generation busy-spins
+/// until the next one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampResolution {
+    /// One tick per millisecond (the default).
+    Milliseconds,
+    /// Ten ticks per millisecond, i.e. 100µs per tick.
+    HundredMicros,
+    /// A hundred ticks per millisecond, i.e. 10µs per tick.
+    TenMicros,
+}
+
+impl TimestampResolution {
+    /// Number of ticks per millisecond at this resolution.
+    fn ticks_per_ms(self) -> u128 {
+        match self {
+            Self::Milliseconds => 1,
+            Self::HundredMicros => 10,
+            Self::TenMicros => 100,
+        }
+    }
+
+    /// Converts a [`Duration`] into a tick count at this resolution.
+    fn ticks_from_duration(self, duration: Duration) -> u128 {
+        match self {
+            Self::Milliseconds => duration.as_millis(),
+            Self::HundredMicros => duration.as_micros() / 100,
+            Self::TenMicros => duration.as_micros() / 10,
+        }
+    }
+}
+
+/// Source of the "current time" a [`SnowflakeGenerator`] embeds into each
+/// [`Snowflake`], in ticks since the UNIX epoch at a given [`TimestampResolution`].
+#[derive(Debug)]
+enum Clock {
+    /// Reads `SystemTime::now()` on every call, i.e. the wall clock. Matches the
+    /// original, pre-[`SnowflakeBuilder::monotonic_clock`] behavior: timestamps track
+    /// NTP adjustments, but the generator must spin out a backwards clock step.
+    Wall { resolution: TimestampResolution },
+    /// Anchors to a `SystemTime` reading taken once at construction and advances it
+    /// using `Instant::elapsed()`, which never goes backwards. Set via
+    /// [`SnowflakeBuilder::monotonic_clock`].
+    Monotonic {
+        resolution: TimestampResolution,
+        start_ticks: u128,
+        start_instant: Instant,
+    },
+}
+
+impl Clock {
+    fn now_ticks(&self) -> u128 {
+        match self {
+            Clock::Wall { resolution } => resolution.ticks_from_duration(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Time went backwards"),
+            ),
+            Clock::Monotonic {
+                resolution,
+                start_ticks,
+                start_instant,
+            } => start_ticks + resolution.ticks_from_duration(start_instant.elapsed()),
+        }
+    }
+}
+
+/// Returns a mask covering the low `bits` bits of a `u64`, i.e. `(1 << bits) - 1`.
+///
+/// `1u64 << 64` is itself an overflow, so a field width of exactly 64 bits is handled as
+/// a special case rather than shifted.
+fn bit_mask(bits: u32) -> u64 {
+    if bits >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << bits) - 1
+    }
+}
+
+/// Shifts `value` left by `shift` bits, treating a `shift` of 64 or more (which would
+/// otherwise overflow the shift) as shifting every bit out, i.e. `0`.
+///
+/// A zero-width timestamp field (`timestamp_bits(0)`) resolves to exactly this shift
+/// amount, since `timestamp_shift` is then the full 64 bits of node id and sequence.
+fn shl_to_zero(value: u64, shift: u32) -> u64 {
+    value.checked_shl(shift).unwrap_or(0)
+}
+
+/// Shifts `value` right by `shift` bits, treating a `shift` of 64 or more the same way
+/// [`shl_to_zero`] does: the result is `0`, since every bit has shifted out.
+fn shr_to_zero(value: u64, shift: u32) -> u64 {
+    value.checked_shr(shift).unwrap_or(0)
+}
+
+/// Builds a [`SnowflakeGenerator`] with a custom epoch and field widths.
+///
+/// Defaults match [`SnowflakeGenerator::new()`]: the crate `EPOCH` with
+/// a 22-bit sequence and no node id.
+#[derive(Debug, Clone)]
+pub struct SnowflakeBuilder {
+    epoch: u128,
+    timestamp_bits: u32,
+    node_bits: u32,
+    sequence_bits: u32,
+    node_id: Option<u16>,
+    sign_bit_safe: bool,
+    monotonic: bool,
+    resolution: TimestampResolution,
+}
+
+impl SnowflakeBuilder {
+    /// Starts a new [`SnowflakeBuilder`] with the default layout.
+    pub fn new() -> Self {
+        Self {
+            epoch: EPOCH,
+            timestamp_bits: 64 - SEQUENCE_BITS,
+            node_bits: 0,
+            sequence_bits: SEQUENCE_BITS,
+            node_id: None,
+            sign_bit_safe: false,
+            monotonic: false,
+            resolution: TimestampResolution::Milliseconds,
+        }
+    }
+
+    /// Sets the epoch against which generated timestamps are measured.
+    pub fn epoch(mut self, epoch: SystemTime) -> Self {
+        self.epoch = epoch
+            .duration_since(UNIX_EPOCH)
+            .expect("epoch must not precede the UNIX epoch")
+            .as_millis();
+        self
+    }
+
+    /// Sets the epoch, as a millisecond-based UNIX timestamp, against which generated
+    /// timestamps are measured.
+    pub fn epoch_millis(mut self, epoch_millis: u128) -> Self {
+        self.epoch = epoch_millis;
+        self
+    }
+
+    /// Sets the width, in bits, of the timestamp field.
+    pub fn timestamp_bits(mut self, bits: u32) -> Self {
+        self.timestamp_bits = bits;
+        self
+    }
+
+    /// Sets the width, in bits, of the node-id field.
+    pub fn node_bits(mut self, bits: u32) -> Self {
+        self.node_bits = bits;
+        self
+    }
+
+    /// Sets the width, in bits, of the sequence field.
+    pub fn sequence_bits(mut self, bits: u32) -> Self {
+        self.sequence_bits = bits;
+        self
+    }
+
+    /// Sets the node id tagged onto every [`Snowflake`] generated from this builder.
+    ///
+    /// Silently has no effect unless combined with a non-zero
+    /// [`SnowflakeBuilder::node_bits`], since there is no node-id field to tag it into.
+    pub fn node_id(mut self, node_id: u16) -> Self {
+        self.node_id = Some(node_id);
+        self
+    }
+
+    /// Reserves bit 63 so every generated [`Snowflake`] is representable as a positive
+    /// `i64`, safe for storage in a signed `BIGINT` column and still monotonically
+    /// sortable as a signed integer.
+    ///
+    /// Shrinks the timestamp field by one bit to make room. Call this before any
+    /// explicit [`SnowflakeBuilder::timestamp_bits`] call, since it adjusts the current
+    /// value rather than the eventual total.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the timestamp field is already 0 bits wide.
+    pub fn sign_bit_safe(mut self) -> Self {
+        self.sign_bit_safe = true;
+        self.timestamp_bits = self
+            .timestamp_bits
+            .checked_sub(1)
+            .expect("timestamp_bits must be at least 1 to reserve a sign bit");
+        self
+    }
+
+    /// Anchors the generator's clock to a monotonic [`Instant`] reference instead of
+    /// reading the wall clock on every call.
+    ///
+    /// At build time this captures the current wall-clock time once, alongside an
+    /// `Instant`, and from then on derives the logical timestamp as
+    /// `start_ts + start_instant.elapsed()`. Since `Instant` never goes backwards, this
+    /// eliminates the backwards-clock spin entirely, at the cost of the embedded
+    /// timestamp no longer tracking NTP adjustments to the wall clock made after
+    /// construction. Leave unset to keep embedding the live wall clock.
+    pub fn monotonic_clock(mut self) -> Self {
+        self.monotonic = true;
+        self
+    }
+
+    /// Sets the resolution at which the timestamp field advances, trading timestamp
+    /// lifespan for sequence headroom under bursty load.
+    pub fn timestamp_resolution(mut self, resolution: TimestampResolution) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Builds the [`SnowflakeGenerator`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestamp_bits + node_bits + sequence_bits` does not equal 64 (or 63
+    /// if [`SnowflakeBuilder::sign_bit_safe`] was set), or if `node_id` does not fit in
+    /// `node_bits` bits.
+    pub fn build(self) -> SnowflakeGenerator {
+        let total_bits = self.timestamp_bits + self.node_bits + self.sequence_bits;
+        let available_bits = if self.sign_bit_safe { 63 } else { 64 };
+        assert!(
+            total_bits == available_bits,
+            "timestamp_bits + node_bits + sequence_bits must sum to {available_bits}, got {total_bits}"
+        );
+
+        let sequence_mask = bit_mask(self.sequence_bits);
+        let node_mask = bit_mask(self.node_bits);
+        let node_shift = self.sequence_bits;
+        let timestamp_shift = self.node_bits + self.sequence_bits;
+        let low_mask = bit_mask(timestamp_shift);
+
+        let layout = SnowflakeLayout {
+            epoch_ticks: self.epoch * self.resolution.ticks_per_ms(),
+            resolution: self.resolution,
+            sequence_mask,
+            node_mask,
+            timestamp_shift,
+            node_shift,
+            low_mask,
+        };
+
+        let node_component = match self.node_id {
+            Some(node_id) if node_mask > 0 => {
+                assert!(
+                    u64::from(node_id) <= node_mask,
+                    "node_id must be less than {}",
+                    node_mask + 1
+                );
+                u64::from(node_id) << node_shift
+            }
+            _ => 0,
+        };
+
+        let clock = if self.monotonic {
+            let duration = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards");
+            Clock::Monotonic {
+                resolution: self.resolution,
+                start_ticks: self.resolution.ticks_from_duration(duration),
+                start_instant: Instant::now(),
+            }
+        } else {
+            Clock::Wall {
+                resolution: self.resolution,
+            }
+        };
+
+        let now_ticks = clock.now_ticks();
+        let initial_id = Snowflake::new(&layout, now_ticks, node_component);
+        SnowflakeGenerator {
+            last_state: AtomicU64::new(initial_id.0),
+            layout,
+            node_component,
+            clock,
+        }
+    }
+}
+
+impl Default for SnowflakeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// A thread-safe, lock-free Snowflake generator.
 ///
-/// Initialize with [`SnowflakeGenerator::new()`].
+/// Initialize with [`SnowflakeGenerator::new()`], [`SnowflakeGenerator::with_node()`], or
+/// [`SnowflakeGenerator::builder()`] for full control over the epoch and field widths.
 #[derive(Debug)]
 pub struct SnowflakeGenerator {
     /// Last generated snowflake.
     last_state: AtomicU64,
+    /// Resolved bit layout, shared by every [`Snowflake`] this generator produces.
+    layout: SnowflakeLayout,
+    /// Node id, pre-shifted into its bit position so it can be OR-ed straight into newly
+    /// generated bit patterns. Zero when no node id is configured.
+    node_component: u64,
+    /// Source of the "current time" embedded into each generated [`Snowflake`].
+    clock: Clock,
 }
 
 impl SnowflakeGenerator {
@@ -56,11 +384,33 @@ impl SnowflakeGenerator {
     ///
     /// The initial state is set to the current time with sequence 0.
     pub fn new() -> Self {
-        let now_ms = unix_timestamp_now_ms();
-        let initial_id = Snowflake::new(now_ms, 0);
-        Self {
-            last_state: AtomicU64::new(initial_id.0),
-        }
+        SnowflakeBuilder::new().build()
+    }
+
+    /// Initializes a new [`SnowflakeGenerator`] that tags every generated [`Snowflake`]
+    /// with `node_id`, so that several processes can share an ID space without
+    /// collisions as long as each uses a distinct node id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node_id` does not fit in 10 bits (i.e. is not `< 1024`).
+    pub fn with_node(node_id: u16) -> Self {
+        SnowflakeBuilder::new()
+            .node_bits(NODE_BITS)
+            .sequence_bits(DISTRIBUTED_SEQUENCE_BITS)
+            .node_id(node_id)
+            .build()
+    }
+
+    /// Starts a [`SnowflakeBuilder`] for full control over the epoch and field widths.
+    pub fn builder() -> SnowflakeBuilder {
+        SnowflakeBuilder::new()
+    }
+
+    /// Returns the resolved [`SnowflakeLayout`] used by this generator, needed to decode
+    /// the [`Snowflake`]-s it produces.
+    pub fn layout(&self) -> SnowflakeLayout {
+        self.layout
     }
 
     /// Generates a [`Snowflake`].
@@ -70,23 +420,27 @@ impl SnowflakeGenerator {
         let mut current_bits = self.last_state.load(AtomicOrdering::Relaxed);
 
         loop {
-            let last_ts = current_bits >> SEQUENCE_BITS;
-            let last_seq = current_bits & SEQUENCE_MASK;
-            
-            let now_ms = unix_timestamp_now_ms();
-            let now_ts = u64::try_from(now_ms.saturating_sub(EPOCH))
+            let last_ts = shr_to_zero(current_bits, self.layout.timestamp_shift);
+            let last_seq = current_bits & self.layout.sequence_mask;
+
+            let now_ticks = self.clock.now_ticks();
+            let now_ts = u64::try_from(now_ticks.saturating_sub(self.layout.epoch_ticks))
                 .expect("Timestamp exceeds u64 capacity");
 
             let next_bits = match now_ts.cmp(&last_ts) {
-                Ordering::Greater => now_ts << SEQUENCE_BITS,
+                Ordering::Greater => {
+                    shl_to_zero(now_ts, self.layout.timestamp_shift) | self.node_component
+                }
                 Ordering::Equal => {
                     let next_seq = last_seq + 1;
-                    if next_seq > SEQUENCE_MASK {
+                    if next_seq > self.layout.sequence_mask {
                         spin_loop();
                         current_bits = self.last_state.load(AtomicOrdering::Relaxed);
                         continue;
                     }
-                    (last_ts << SEQUENCE_BITS) | next_seq
+                    shl_to_zero(last_ts, self.layout.timestamp_shift)
+                        | self.node_component
+                        | next_seq
                 }
                 Ordering::Less => {
                     spin_loop();
@@ -108,23 +462,119 @@ impl SnowflakeGenerator {
     }
 }
 
+impl Default for SnowflakeGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// [`Snowflake`] wrapper.
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Snowflake(pub u64);
 
+/// The individually decoded components of a [`Snowflake`], as returned by
+/// [`Snowflake::decompose`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnowflakeParts {
+    /// Millisecond-based UNIX timestamp, relative to the UNIX epoch.
+    pub timestamp_ms: u128,
+    /// Node id, or 0 if the originating generator had no node-id field configured.
+    pub node_id: u16,
+    /// Sequence number within the timestamp (and node id, if any).
+    pub sequence: u64,
+}
+
 impl Snowflake {
-    /// Construct a [`Snowflake`] from timestamp and sequence.
-    fn new(timestamp: u128, sequence: u64) -> Self {
-        let shifted = u64::try_from(timestamp - EPOCH).expect("Timestamp overflow") << SEQUENCE_BITS;
-        Snowflake(shifted | (sequence & SEQUENCE_MASK))
+    /// Construct a [`Snowflake`] from a layout, a timestamp in ticks since the UNIX
+    /// epoch, and the bits below the timestamp (node id and sequence, already shifted
+    /// and combined by the caller).
+    fn new(layout: &SnowflakeLayout, timestamp_ticks: u128, low_bits: u64) -> Self {
+        let ticks = u64::try_from(timestamp_ticks - layout.epoch_ticks).expect("Timestamp overflow");
+        let shifted = shl_to_zero(ticks, layout.timestamp_shift);
+        Snowflake(shifted | (low_bits & layout.low_mask))
     }
 
-    /// Extract the millisecond-based UNIX timestamp of a [`Snowflake`].
+    /// Extract the millisecond-based UNIX timestamp of a [`Snowflake`], given the
+    /// [`SnowflakeLayout`] of the generator that produced it.
     ///
     /// The resulting timestamp is relative to the UNIX epoch, Jan 01 1970 00:00:00 GMT+0000
-    pub fn extract_unix_timestamp(&self) -> u128 {
-        u128::from(self.0 >> SEQUENCE_BITS) + EPOCH
+    pub fn extract_unix_timestamp(&self, layout: &SnowflakeLayout) -> u128 {
+        let ticks_since_unix_epoch =
+            u128::from(shr_to_zero(self.0, layout.timestamp_shift)) + layout.epoch_ticks;
+        ticks_since_unix_epoch / layout.resolution.ticks_per_ms()
+    }
+
+    /// Extract the node id of a [`Snowflake`], given the [`SnowflakeLayout`] of the
+    /// generator that produced it.
+    ///
+    /// Returns 0 if `layout` was not configured with a node-id field.
+    pub fn extract_node_id(&self, layout: &SnowflakeLayout) -> u16 {
+        (shr_to_zero(self.0, layout.node_shift) & layout.node_mask) as u16
+    }
+
+    /// Extract the sequence number of a [`Snowflake`], given the [`SnowflakeLayout`] of
+    /// the generator that produced it.
+    pub fn sequence(&self, layout: &SnowflakeLayout) -> u64 {
+        self.0 & layout.sequence_mask
+    }
+
+    /// Decomposes a [`Snowflake`] into its [`SnowflakeParts`], given the
+    /// [`SnowflakeLayout`] of the generator that produced it.
+    pub fn decompose(&self, layout: &SnowflakeLayout) -> SnowflakeParts {
+        SnowflakeParts {
+            timestamp_ms: self.extract_unix_timestamp(layout),
+            node_id: self.extract_node_id(layout),
+            sequence: self.sequence(layout),
+        }
+    }
+
+    /// Reinterprets this [`Snowflake`]'s bits as a signed `i64`.
+    ///
+    /// Only guaranteed to be positive, and therefore monotonically sortable as a signed
+    /// integer, when the originating generator was built with
+    /// [`SnowflakeBuilder::sign_bit_safe`]. If that isn't guaranteed, prefer
+    /// `i64::try_from` instead, which checks bit 63.
+    pub fn as_i64(&self) -> i64 {
+        self.0 as i64
+    }
+
+    /// Encodes this [`Snowflake`] as a compact, URL-safe base62 string, rather than the
+    /// 19-20 digit decimal `u64` it wraps.
+    pub fn to_base62(&self) -> String {
+        if self.0 == 0 {
+            return "0".to_string();
+        }
+
+        let mut digits = Vec::new();
+        let mut remaining = self.0;
+        while remaining > 0 {
+            digits.push(BASE62_ALPHABET[(remaining % 62) as usize]);
+            remaining /= 62;
+        }
+        digits.reverse();
+        String::from_utf8(digits).expect("base62 alphabet is ASCII")
+    }
+
+    /// Decodes a [`Snowflake`] from a base62 string produced by
+    /// [`Snowflake::to_base62`].
+    pub fn from_base62(s: &str) -> Result<Self, Base62DecodeError> {
+        if s.is_empty() {
+            return Err(Base62DecodeError::Empty);
+        }
+
+        let mut value: u64 = 0;
+        for byte in s.bytes() {
+            let digit = BASE62_ALPHABET
+                .iter()
+                .position(|&b| b == byte)
+                .ok_or(Base62DecodeError::InvalidChar(byte as char))?;
+            value = value
+                .checked_mul(62)
+                .and_then(|v| v.checked_add(digit as u64))
+                .ok_or(Base62DecodeError::Overflow)?;
+        }
+        Ok(Snowflake(value))
     }
 }
 
@@ -134,6 +584,123 @@ impl From<Snowflake> for u64 {
     }
 }
 
+impl std::fmt::Display for Snowflake {
+    /// Formats this [`Snowflake`] as its compact base62 encoding; see
+    /// [`Snowflake::to_base62`].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_base62())
+    }
+}
+
+impl std::str::FromStr for Snowflake {
+    type Err = Base62DecodeError;
+
+    /// Parses a [`Snowflake`] from its compact base62 encoding; see
+    /// [`Snowflake::from_base62`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_base62(s)
+    }
+}
+
+/// Alphabet used by [`Snowflake::to_base62`] and [`Snowflake::from_base62`]: digits,
+/// then uppercase, then lowercase letters.
+const BASE62_ALPHABET: &[u8; 62] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Error returned by [`Snowflake::from_base62`] (and the corresponding `FromStr` impl).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base62DecodeError {
+    /// The string was empty.
+    Empty,
+    /// The string contained a byte outside the base62 alphabet.
+    InvalidChar(char),
+    /// The decoded value does not fit in a `u64`.
+    Overflow,
+}
+
+impl std::fmt::Display for Base62DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "base62 string is empty"),
+            Self::InvalidChar(c) => write!(f, "'{c}' is not a valid base62 character"),
+            Self::Overflow => write!(f, "base62 string decodes to a value larger than u64::MAX"),
+        }
+    }
+}
+
+impl std::error::Error for Base62DecodeError {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Snowflake {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Snowflake {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(Snowflake)
+    }
+}
+
+/// Serde (de)serialization as a compact base62 string instead of [`Snowflake`]'s default
+/// raw `u64` representation. Opt in per-field with `#[serde(with = "snof::base62")]`.
+#[cfg(feature = "serde")]
+pub mod base62 {
+    use super::Snowflake;
+    use serde::{Deserialize, Serialize};
+
+    pub fn serialize<S>(snowflake: &Snowflake, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        snowflake.to_base62().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Snowflake, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let encoded = String::deserialize(deserializer)?;
+        Snowflake::from_base62(&encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Error returned by `TryFrom<Snowflake> for i64` when bit 63 of the snowflake is set,
+/// e.g. one produced by a generator built without [`SnowflakeBuilder::sign_bit_safe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignBitSetError;
+
+impl std::fmt::Display for SignBitSetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "snowflake has bit 63 set and cannot be represented as a positive i64"
+        )
+    }
+}
+
+impl std::error::Error for SignBitSetError {}
+
+impl TryFrom<Snowflake> for i64 {
+    type Error = SignBitSetError;
+
+    fn try_from(value: Snowflake) -> Result<Self, Self::Error> {
+        if value.0 & (1 << 63) == 0 {
+            Ok(value.0 as i64)
+        } else {
+            Err(SignBitSetError)
+        }
+    }
+}
+
 /// Gets the current millisecond-based UNIX timestamp.
 pub fn unix_timestamp_now_ms() -> u128 {
     SystemTime::now()
@@ -141,3 +708,187 @@ pub fn unix_timestamp_now_ms() -> u128 {
         .expect("Time went backwards")
         .as_millis()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_node_tags_every_snowflake_with_the_node_id() {
+        let generator = SnowflakeGenerator::with_node(7);
+        let layout = generator.layout();
+
+        for _ in 0..16 {
+            let id = generator.generate();
+            assert_eq!(id.extract_node_id(&layout), 7);
+        }
+    }
+
+    #[test]
+    fn node_id_without_node_bits_is_a_no_op() {
+        let generator = SnowflakeBuilder::new().node_id(42).build();
+        let layout = generator.layout();
+
+        let id = generator.generate();
+        assert_eq!(id.extract_node_id(&layout), 0);
+    }
+
+    #[test]
+    fn sign_bit_safe_keeps_bit_63_clear_and_converts_to_i64() {
+        let generator = SnowflakeBuilder::new().sign_bit_safe().build();
+
+        for _ in 0..16 {
+            let id = generator.generate();
+            assert_eq!(id.0 & (1 << 63), 0);
+            assert_eq!(id.as_i64(), i64::try_from(id).unwrap());
+            assert!(i64::try_from(id).unwrap() >= 0);
+        }
+    }
+
+    #[test]
+    fn try_from_i64_rejects_bit_63_set() {
+        let id = Snowflake(1 << 63);
+        assert_eq!(i64::try_from(id), Err(SignBitSetError));
+    }
+
+    #[test]
+    fn decompose_recovers_node_id_and_sequence() {
+        let generator = SnowflakeGenerator::with_node(3);
+        let layout = generator.layout();
+
+        let first = generator.generate();
+        let second = generator.generate();
+        let parts = second.decompose(&layout);
+
+        assert_eq!(parts.node_id, 3);
+        assert_eq!(parts.timestamp_ms, second.extract_unix_timestamp(&layout));
+        assert_eq!(parts.sequence, second.sequence(&layout));
+        if first.extract_unix_timestamp(&layout) == parts.timestamp_ms {
+            assert_eq!(parts.sequence, first.sequence(&layout) + 1);
+        }
+    }
+
+    #[test]
+    fn sub_millisecond_resolution_round_trips_through_extraction() {
+        let generator = SnowflakeBuilder::new()
+            .timestamp_resolution(TimestampResolution::HundredMicros)
+            .build();
+        let layout = generator.layout();
+
+        let id = generator.generate();
+        let now_ms = unix_timestamp_now_ms();
+
+        // The embedded timestamp is still reported in milliseconds, even though the
+        // underlying tick width is 100µs.
+        assert!(id.extract_unix_timestamp(&layout).abs_diff(now_ms) < 1000);
+    }
+
+    #[test]
+    fn base62_round_trips_zero_and_max() {
+        for value in [0, 1, 61, 62, u64::MAX] {
+            let id = Snowflake(value);
+            let encoded = id.to_base62();
+            assert_eq!(Snowflake::from_base62(&encoded).unwrap(), id);
+            assert_eq!(encoded.parse::<Snowflake>().unwrap(), id);
+            assert_eq!(id.to_string(), encoded);
+        }
+    }
+
+    #[test]
+    fn base62_zero_encodes_as_single_digit() {
+        assert_eq!(Snowflake(0).to_base62(), "0");
+    }
+
+    #[test]
+    fn base62_rejects_empty_string() {
+        assert_eq!(Snowflake::from_base62(""), Err(Base62DecodeError::Empty));
+    }
+
+    #[test]
+    fn base62_rejects_invalid_char() {
+        assert_eq!(
+            Snowflake::from_base62("abc!"),
+            Err(Base62DecodeError::InvalidChar('!'))
+        );
+    }
+
+    #[test]
+    fn base62_rejects_overflow() {
+        // One more base62 digit than u64::MAX ("LygHa16AHYF") can hold.
+        let too_large = format!("1{}", Snowflake(u64::MAX).to_base62());
+        assert_eq!(
+            Snowflake::from_base62(&too_large),
+            Err(Base62DecodeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn monotonic_clock_tracks_elapsed_time_and_never_regresses() {
+        let generator = SnowflakeBuilder::new().monotonic_clock().build();
+        let layout = generator.layout();
+        let before = unix_timestamp_now_ms();
+
+        let first = generator.generate();
+        std::thread::sleep(Duration::from_millis(5));
+        let second = generator.generate();
+
+        assert!(second.0 > first.0);
+        assert!(
+            second.extract_unix_timestamp(&layout) >= first.extract_unix_timestamp(&layout) + 4
+        );
+        // The embedded timestamp advances with `Instant::elapsed()`, not fresh reads of
+        // the wall clock, but both should still agree within a generous margin.
+        assert!(second.extract_unix_timestamp(&layout).abs_diff(before) < 1000);
+    }
+
+    #[test]
+    fn builder_custom_epoch_and_bit_widths_round_trip() {
+        let custom_epoch = 1_700_000_000_000u128;
+        let generator = SnowflakeBuilder::new()
+            .epoch_millis(custom_epoch)
+            .timestamp_bits(40)
+            .node_bits(8)
+            .sequence_bits(16)
+            .node_id(200)
+            .build();
+        let layout = generator.layout();
+
+        let id = generator.generate();
+        let parts = id.decompose(&layout);
+
+        assert_eq!(parts.node_id, 200);
+        assert!(parts.timestamp_ms.abs_diff(unix_timestamp_now_ms()) < 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "must sum to")]
+    fn builder_panics_when_bit_widths_dont_sum_to_64() {
+        SnowflakeBuilder::new()
+            .timestamp_bits(10)
+            .node_bits(10)
+            .sequence_bits(10)
+            .build();
+    }
+
+    #[test]
+    fn builder_accepts_a_full_64_bit_sequence_field() {
+        // A single field claiming all 64 bits must not overflow the `1 << bits` shift
+        // used to build its mask, nor the shifts by a full-width `timestamp_shift`
+        // elsewhere in `generate()`/`extract_unix_timestamp()`.
+        let generator = SnowflakeBuilder::new()
+            .timestamp_bits(0)
+            .node_bits(0)
+            .sequence_bits(64)
+            .build();
+        let layout = generator.layout();
+
+        let id = generator.generate();
+        assert_eq!(id.sequence(&layout), id.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp_bits must be at least 1")]
+    fn sign_bit_safe_panics_on_zero_width_timestamp() {
+        SnowflakeBuilder::new().timestamp_bits(0).sign_bit_safe();
+    }
+}